@@ -0,0 +1,161 @@
+// Activation functions and numerically stable reductions, with analytic derivatives recorded
+// directly via `unary_op` rather than traced through their naive composite definitions (e.g.
+// `sigmoid` is not built out of `exp`/`div` on the tape -- its derivative `s*(1-s)` is supplied
+// directly). `log_sum_exp`/`softmax` additionally subtract the running max, detached as a
+// constant via `Reverse::auto`, so large logits don't overflow `exp`.
+
+use crate::autograd::{Differentiable, Reverse};
+use num::traits::{One, Zero};
+
+impl<
+        T: Differentiable
+            + Clone
+            + PartialOrd
+            + One
+            + Zero
+            + std::ops::Neg<Output = T>
+            + std::ops::Add<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Mul<Output = T>
+            + std::ops::Div<Output = T>,
+    > Reverse<T>
+{
+    /// `sigmoid(x) = 1 / (1 + exp(-x))`, derivative `s * (1 - s)`.
+    #[inline]
+    pub fn sigmoid(&self) -> Self {
+        self.unary_op(
+            |v| sigmoid_value(v),
+            |v| {
+                let s = sigmoid_value(v);
+                s.clone() * (T::one() - s)
+            },
+        )
+    }
+
+    /// `tanh(x)`, derivative `1 - tanh(x)^2`.
+    #[inline]
+    pub fn tanh(&self) -> Self {
+        self.unary_op(
+            |v| tanh_value(v),
+            |v| {
+                let t = tanh_value(v);
+                T::one() - t.clone() * t
+            },
+        )
+    }
+
+    /// `relu(x) = max(x, 0)`, with the usual sub-gradient of `0` at `x == 0`.
+    #[inline]
+    pub fn relu(&self) -> Self {
+        self.unary_op(
+            |v| if v > T::zero() { v } else { T::zero() },
+            |v| if v > T::zero() { T::one() } else { T::zero() },
+        )
+    }
+
+    /// `leaky_relu(x) = x` for `x > 0`, else `alpha * x`.
+    #[inline]
+    pub fn leaky_relu(&self, alpha: T) -> Self {
+        let alpha_for_value = alpha.clone();
+        self.unary_op(
+            move |v| {
+                if v > T::zero() {
+                    v
+                } else {
+                    v * alpha_for_value.clone()
+                }
+            },
+            move |v| {
+                if v > T::zero() {
+                    T::one()
+                } else {
+                    alpha.clone()
+                }
+            },
+        )
+    }
+}
+
+#[inline]
+fn sigmoid_value<T>(v: T) -> T
+where
+    T: Differentiable + One + std::ops::Neg<Output = T> + std::ops::Add<Output = T> + std::ops::Div<Output = T>,
+{
+    T::one() / (T::one() + (-v).d_exp())
+}
+
+#[inline]
+fn tanh_value<T>(v: T) -> T
+where
+    T: Differentiable
+        + Clone
+        + PartialOrd
+        + One
+        + Zero
+        + std::ops::Neg<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Add<Output = T>
+        + std::ops::Div<Output = T>,
+{
+    // tanh is odd: evaluate it at -|v| and flip the sign back, so `exp` never sees a positive
+    // argument and cannot overflow for large |v|.
+    let positive = v > T::zero();
+    let a = if positive { -v } else { v };
+    let e2x = a.d_scale(2.0).d_exp();
+    let t = (e2x.clone() - T::one()) / (e2x + T::one());
+    if positive {
+        -t
+    } else {
+        t
+    }
+}
+
+/// `lse(x) = m + ln(sum(exp(x_i - m)))`, where `m = max(x)` is detached as a constant via
+/// `Reverse::auto` so large logits don't overflow `exp`.
+pub fn log_sum_exp<T>(xs: &[Reverse<T>]) -> Reverse<T>
+where
+    T: Differentiable
+        + Clone
+        + PartialOrd
+        + One
+        + Zero
+        + std::ops::Neg<Output = T>
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>
+        + std::ops::Div<Output = T>,
+{
+    assert!(!xs.is_empty(), "log_sum_exp of an empty slice");
+
+    let max_value = xs
+        .iter()
+        .map(|x| x.value().clone())
+        .reduce(|a, b| if a > b { a } else { b })
+        .unwrap();
+    let m = Reverse::auto(max_value);
+
+    let mut sum = Reverse::auto(T::zero());
+    for x in xs {
+        sum = &sum + &(x - &m).exp();
+    }
+
+    &m + &sum.ln()
+}
+
+/// `softmax(x)_i = exp(x_i - lse(x))`, i.e. numerically stable via [`log_sum_exp`].
+pub fn softmax<T>(xs: &[Reverse<T>]) -> Vec<Reverse<T>>
+where
+    T: Differentiable
+        + Clone
+        + PartialOrd
+        + One
+        + Zero
+        + std::ops::Neg<Output = T>
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>
+        + std::ops::Div<Output = T>,
+{
+    let lse = log_sum_exp(xs);
+    xs.iter().map(|x| (x - &lse).exp()).collect()
+}
@@ -13,12 +13,16 @@ use std::rc::Rc;
 #[derive(Clone, Debug)]
 pub struct Tape<T> {
     ops: Rc<RefCell<Vec<Op<T>>>>,
+    // Scratch space for `backward()`, kept around and reused across calls (and across
+    // `reset()`s) instead of allocating a fresh `Vec` on every reverse pass.
+    grads: Rc<RefCell<Vec<T>>>,
 }
 
 impl<T: Clone> Tape<T> {
     pub fn new() -> Self {
         Self {
             ops: Rc::new(RefCell::new(vec![])),
+            grads: Rc::new(RefCell::new(vec![])),
         }
     }
 
@@ -272,89 +276,244 @@ impl<T: Clone + Zero + One> Reverse<T> {
         }
     }
 
+    /// Runs the reverse pass and returns a fresh [`Derivatives`]. Convenience wrapper around
+    /// [`Reverse::backward`] for callers that do not care about reusing the gradient buffer
+    /// across calls (e.g. one-off computations, as opposed to an optimizer's hot loop).
     pub fn derivatives(&self) -> Derivatives<T> {
-        if self.tape.is_none() {
-            return Derivatives::empty();
-        }
-        let tape = self.tape.as_ref().unwrap();
+        let mut into = Derivatives::empty();
+        self.backward(&mut into);
+        into
+    }
+
+    /// Runs the reverse pass, writing the result into `into` and reusing `into`'s backing
+    /// storage where possible.
+    ///
+    /// This is seeded at `self`: since the tape is topologically ordered by construction, no
+    /// node with an index greater than `self.index` can be an ancestor of `self`, so the sweep
+    /// starts at `self.index` rather than at the end of the whole tape. That is the only pruning
+    /// this does -- it does NOT additionally skip nodes whose adjoint `.is_zero()`, because for
+    /// `T = Reverse<U>` (nesting, as used for Hessians) an adjoint can have a zero *value* while
+    /// its inner tape still carries nonzero second-order structure that must keep propagating;
+    /// skipping on value alone would silently drop that structure.
+    pub fn backward(&self, into: &mut Derivatives<T>) {
+        let tape = match &self.tape {
+            None => {
+                into.derivatives.clear();
+                return;
+            }
+            Some(tape) => tape,
+        };
         let ops = tape.ops.borrow();
+        let mut grads = tape.grads.borrow_mut();
 
-        let mut derivatives: Vec<T> = vec![T::zero(); ops.len()];
-        derivatives[self.index] = T::one();
+        if grads.len() < ops.len() {
+            grads.resize(ops.len(), T::zero());
+        }
+        for g in grads[..ops.len()].iter_mut() {
+            *g = T::zero();
+        }
+        grads[self.index] = T::one();
 
-        for idx in (0..ops.len()).rev() {
-            derivatives[ops[idx].left] = derivatives[ops[idx].left].clone()
-                + derivatives[idx].clone() * ops[idx].dleft.clone();
-            derivatives[ops[idx].right] = derivatives[ops[idx].right].clone()
-                + derivatives[idx].clone() * ops[idx].dright.clone();
+        for idx in (0..=self.index).rev() {
+            let grad = grads[idx].clone();
+            grads[ops[idx].left] = grads[ops[idx].left].clone() + grad.clone() * ops[idx].dleft.clone();
+            grads[ops[idx].right] =
+                grads[ops[idx].right].clone() + grad * ops[idx].dright.clone();
         }
 
-        Derivatives { derivatives }
+        into.derivatives.clear();
+        into.derivatives.extend_from_slice(&grads[..ops.len()]);
+    }
+}
+
+/// Transcendental functions and their derivative rules, expressed generically over the
+/// underlying numeric type so that `Reverse<T>` can be nested (`Reverse<Reverse<f64>>`) to get
+/// second-order derivatives.
+///
+/// The crucial bit is that every derivative rule here is computed as an operation on `T` itself
+/// (e.g. `sqrt`'s derivative is `x.d_sqrt().d_recip().d_scale(0.5)`) rather than a raw float
+/// intrinsic. When `T = Reverse<f64>`, those operations are themselves traced, so the inner tape
+/// records the second-order terms and a scalar loss built as `Reverse<Reverse<f64>>` yields full
+/// Hessian rows from two calls to `.derivatives()`.
+pub trait Differentiable: Clone {
+    fn d_ln(&self) -> Self;
+    fn d_exp(&self) -> Self;
+    fn d_sqrt(&self) -> Self;
+    fn d_powi(&self, n: i32) -> Self;
+    fn d_abs(&self) -> Self;
+    fn d_signum(&self) -> Self;
+    fn d_recip(&self) -> Self;
+    /// Multiply by a real-valued constant (e.g. the `0.5` in `sqrt`'s derivative, or the `n` in
+    /// `powi`'s), recursing so that nested `Reverse<T>` records the constant as a traced op.
+    fn d_scale(&self, factor: f64) -> Self;
+}
+
+impl Differentiable for f32 {
+    // TODO: no rigorous testing has been done on any of these
+    #[inline]
+    fn d_ln(&self) -> Self {
+        self.ln()
+    }
+
+    #[inline]
+    fn d_exp(&self) -> Self {
+        self.exp()
+    }
+
+    #[inline]
+    fn d_sqrt(&self) -> Self {
+        self.sqrt()
+    }
+
+    #[inline]
+    fn d_powi(&self, n: i32) -> Self {
+        self.powi(n)
+    }
+
+    #[inline]
+    fn d_abs(&self) -> Self {
+        self.abs()
+    }
+
+    #[inline]
+    fn d_signum(&self) -> Self {
+        self.signum()
+    }
+
+    #[inline]
+    fn d_recip(&self) -> Self {
+        self.recip()
+    }
+
+    #[inline]
+    fn d_scale(&self, factor: f64) -> Self {
+        self * (factor as f32)
     }
 }
 
-impl Reverse<f32> {
-    // TODO: no rigorous testing has been done on any of these
+impl Differentiable for f64 {
     #[inline]
-    pub fn ln(&self) -> Self {
-        self.unary_op(|v| v.ln(), |v| v.recip())
+    fn d_ln(&self) -> Self {
+        self.ln()
     }
 
     #[inline]
-    pub fn abs(&self) -> Self {
-        self.unary_op(|v| v.abs(), |v| v.signum())
+    fn d_exp(&self) -> Self {
+        self.exp()
     }
 
     #[inline]
-    pub fn signum(&self) -> Self {
-        self.unary_op(|v| v.signum(), |_| 0.0)
+    fn d_sqrt(&self) -> Self {
+        self.sqrt()
     }
 
     #[inline]
-    pub fn exp(&self) -> Self {
-        self.unary_op(f32::exp, f32::exp)
+    fn d_powi(&self, n: i32) -> Self {
+        self.powi(n)
     }
 
     #[inline]
-    pub fn sqrt(&self) -> Self {
-        self.unary_op(f32::sqrt, |v| 0.5 * v.sqrt().recip())
+    fn d_abs(&self) -> Self {
+        self.abs()
     }
 
     #[inline]
-    pub fn powi(&self, n: i32) -> Self {
-        self.unary_op(|v| v.powi(n), |v| (n as f32) * v.clone().powi(n - 1))
+    fn d_signum(&self) -> Self {
+        self.signum()
+    }
+
+    #[inline]
+    fn d_recip(&self) -> Self {
+        self.recip()
+    }
+
+    #[inline]
+    fn d_scale(&self, factor: f64) -> Self {
+        self * factor
     }
 }
 
-impl Reverse<f64> {
+impl<T: Differentiable + Clone + One + Zero + std::ops::Neg<Output = T> + std::ops::Mul<Output = T>>
+    Reverse<T>
+{
     #[inline]
     pub fn ln(&self) -> Self {
-        self.unary_op(|v| v.ln(), |v| v.recip())
+        self.unary_op(|v| v.d_ln(), |v| v.d_recip())
     }
 
     #[inline]
     pub fn abs(&self) -> Self {
-        self.unary_op(|v| v.abs(), |v| v.signum())
+        self.unary_op(|v| v.d_abs(), |v| v.d_signum())
     }
 
     #[inline]
     pub fn signum(&self) -> Self {
-        self.unary_op(|v| v.signum(), |_| 0.0)
+        self.unary_op(|v| v.d_signum(), |_| T::zero())
     }
 
     #[inline]
     pub fn exp(&self) -> Self {
-        self.unary_op(f64::exp, f64::exp)
+        self.unary_op(|v| v.d_exp(), |v| v.d_exp())
     }
 
     #[inline]
     pub fn sqrt(&self) -> Self {
-        self.unary_op(f64::sqrt, |v| 0.5 * v.sqrt().recip())
+        self.unary_op(|v| v.d_sqrt(), |v| v.d_sqrt().d_recip().d_scale(0.5))
     }
 
     #[inline]
     pub fn powi(&self, n: i32) -> Self {
-        self.unary_op(|v| v.powi(n), |v| (n as f64) * v.clone().powi(n - 1))
+        self.unary_op(|v| v.d_powi(n), |v| v.d_powi(n - 1).d_scale(n as f64))
+    }
+}
+
+impl<T: Differentiable + Clone + One + Zero + std::ops::Neg<Output = T> + std::ops::Mul<Output = T>>
+    Differentiable for Reverse<T>
+{
+    #[inline]
+    fn d_ln(&self) -> Self {
+        self.ln()
+    }
+
+    #[inline]
+    fn d_exp(&self) -> Self {
+        self.exp()
+    }
+
+    #[inline]
+    fn d_sqrt(&self) -> Self {
+        self.sqrt()
+    }
+
+    #[inline]
+    fn d_powi(&self, n: i32) -> Self {
+        self.powi(n)
+    }
+
+    #[inline]
+    fn d_abs(&self) -> Self {
+        self.abs()
+    }
+
+    #[inline]
+    fn d_signum(&self) -> Self {
+        self.signum()
+    }
+
+    #[inline]
+    fn d_recip(&self) -> Self {
+        self.unary_op(
+            |v| v.d_recip(),
+            |v| {
+                let r = v.d_recip();
+                -(r.clone() * r)
+            },
+        )
+    }
+
+    #[inline]
+    fn d_scale(&self, factor: f64) -> Self {
+        self.unary_op(|v| v.d_scale(factor), |_| T::one().d_scale(factor))
     }
 }
 
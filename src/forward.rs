@@ -0,0 +1,370 @@
+// Forward-mode automatic differentiation, as a companion to the reverse-mode `Reverse<T>` in
+// `autograd.rs`. Loosely modeled on easy_ml's `Trace` record: no tape, no `Rc`, just a value
+// paired with its tangent, propagated forward through each operation.
+//
+// Forward mode is the better fit when there are few inputs and many outputs (or you only need a
+// single directional derivative): seed the input you care about with tangent 1 and everything
+// else with tangent 0, run the computation once, and the tangent that falls out of the result is
+// that column of the Jacobian. No tape to build or reverse.
+
+use num::traits::{One, Zero};
+
+/// A dual number: a value paired with its tangent (directional derivative).
+///
+/// Unlike `Reverse<T>`, `Trace<T>` carries no tape and no `Rc` -- it is a plain owned struct, so
+/// it is `Copy` whenever `T` is.
+#[derive(Clone, Copy, Debug)]
+pub struct Trace<T> {
+    pub(crate) value: T,
+    pub(crate) tangent: T,
+}
+
+impl<T: PartialEq> PartialEq for Trace<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Trace<T> {}
+
+impl<T: PartialOrd> PartialOrd for Trace<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for Trace<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Clone + Zero> Trace<T> {
+    /// A constant: its tangent is zero, so it contributes nothing to any derivative.
+    #[inline]
+    pub fn constant(value: T) -> Self {
+        Trace {
+            value,
+            tangent: T::zero(),
+        }
+    }
+}
+
+impl<T: Clone + One> Trace<T> {
+    /// A seeded variable: its tangent is one, so a computation built from it yields that
+    /// variable's column of the Jacobian directly in `.tangent`.
+    #[inline]
+    pub fn variable(value: T) -> Self {
+        Trace {
+            value,
+            tangent: T::one(),
+        }
+    }
+}
+
+impl<T: Clone> Trace<T> {
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    #[inline]
+    pub fn tangent(&self) -> &T {
+        &self.tangent
+    }
+
+    /// A value with an explicit tangent, for seeding one input of several: give the input you
+    /// want the derivative of a tangent of one and every other input a tangent of zero.
+    #[inline]
+    pub fn seeded(value: T, tangent: T) -> Self {
+        Trace { value, tangent }
+    }
+
+    #[inline]
+    pub fn unary_op<F, F2>(&self, eval: F, deriv: F2) -> Self
+    where
+        F: Fn(T) -> T,
+        F2: Fn(T) -> T,
+        T: std::ops::Mul<Output = T>,
+    {
+        Trace {
+            value: eval(self.value.clone()),
+            tangent: deriv(self.value.clone()) * self.tangent.clone(),
+        }
+    }
+
+    #[inline]
+    pub fn bin_op<F, F2, F3>(&self, other: &Trace<T>, eval: F, deriv_left: F2, deriv_right: F3) -> Self
+    where
+        F: Fn(T, T) -> T,
+        F2: Fn(T, T) -> T,
+        F3: Fn(T, T) -> T,
+        T: std::ops::Mul<Output = T> + std::ops::Add<Output = T>,
+    {
+        Trace {
+            value: eval(self.value.clone(), other.value.clone()),
+            tangent: deriv_left(self.value.clone(), other.value.clone()) * self.tangent.clone()
+                + deriv_right(self.value.clone(), other.value.clone()) * other.tangent.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Zero + One> Zero for Trace<T> {
+    #[inline]
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<T: Clone + PartialEq + Zero + One> One for Trace<T> {
+    #[inline]
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    #[inline]
+    fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+}
+
+impl Trace<f32> {
+    // TODO: no rigorous testing has been done on any of these
+    #[inline]
+    pub fn ln(&self) -> Self {
+        self.unary_op(|v| v.ln(), |v| v.recip())
+    }
+
+    #[inline]
+    pub fn abs(&self) -> Self {
+        self.unary_op(|v| v.abs(), |v| v.signum())
+    }
+
+    #[inline]
+    pub fn signum(&self) -> Self {
+        self.unary_op(|v| v.signum(), |_| 0.0)
+    }
+
+    #[inline]
+    pub fn exp(&self) -> Self {
+        self.unary_op(f32::exp, f32::exp)
+    }
+
+    #[inline]
+    pub fn sqrt(&self) -> Self {
+        self.unary_op(f32::sqrt, |v| 0.5 * v.sqrt().recip())
+    }
+
+    #[inline]
+    pub fn powi(&self, n: i32) -> Self {
+        self.unary_op(|v| v.powi(n), |v| (n as f32) * v.clone().powi(n - 1))
+    }
+}
+
+impl Trace<f64> {
+    #[inline]
+    pub fn ln(&self) -> Self {
+        self.unary_op(|v| v.ln(), |v| v.recip())
+    }
+
+    #[inline]
+    pub fn abs(&self) -> Self {
+        self.unary_op(|v| v.abs(), |v| v.signum())
+    }
+
+    #[inline]
+    pub fn signum(&self) -> Self {
+        self.unary_op(|v| v.signum(), |_| 0.0)
+    }
+
+    #[inline]
+    pub fn exp(&self) -> Self {
+        self.unary_op(f64::exp, f64::exp)
+    }
+
+    #[inline]
+    pub fn sqrt(&self) -> Self {
+        self.unary_op(f64::sqrt, |v| 0.5 * v.sqrt().recip())
+    }
+
+    #[inline]
+    pub fn powi(&self, n: i32) -> Self {
+        self.unary_op(|v| v.powi(n), |v| (n as f64) * v.clone().powi(n - 1))
+    }
+}
+
+impl<T: Clone + std::ops::Add<Output = T>> std::ops::Add for Trace<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Trace {
+            value: self.value + other.value,
+            tangent: self.tangent + other.tangent,
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Add<Output = T>> std::ops::Add for &Trace<T> {
+    type Output = Trace<T>;
+
+    #[inline]
+    fn add(self, other: Self) -> Self::Output {
+        Trace {
+            value: self.value.clone() + other.value.clone(),
+            tangent: self.tangent.clone() + other.tangent.clone(),
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Add<Output = T>> std::ops::AddAssign for Trace<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        *self = self.clone() + other;
+    }
+}
+
+impl<T: Clone + std::ops::Sub<Output = T>> std::ops::Sub for Trace<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Trace {
+            value: self.value - other.value,
+            tangent: self.tangent - other.tangent,
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Sub<Output = T>> std::ops::Sub for &Trace<T> {
+    type Output = Trace<T>;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self::Output {
+        Trace {
+            value: self.value.clone() - other.value.clone(),
+            tangent: self.tangent.clone() - other.tangent.clone(),
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Sub<Output = T>> std::ops::SubAssign for Trace<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.clone() - other;
+    }
+}
+
+impl<T: Clone + std::ops::Mul<Output = T> + std::ops::Add<Output = T>> std::ops::Mul for Trace<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Trace {
+            value: self.value.clone() * other.value.clone(),
+            tangent: self.value * other.tangent + other.value * self.tangent,
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Mul<Output = T> + std::ops::Add<Output = T>> std::ops::Mul for &Trace<T> {
+    type Output = Trace<T>;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self::Output {
+        Trace {
+            value: self.value.clone() * other.value.clone(),
+            tangent: self.value.clone() * other.tangent.clone()
+                + other.value.clone() * self.tangent.clone(),
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Mul<Output = T> + std::ops::Add<Output = T>> std::ops::MulAssign
+    for Trace<T>
+{
+    #[inline]
+    fn mul_assign(&mut self, other: Self) {
+        *self = self.clone() * other;
+    }
+}
+
+impl<T: Clone + std::ops::Neg<Output = T>> std::ops::Neg for Trace<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Trace {
+            value: -self.value,
+            tangent: -self.tangent,
+        }
+    }
+}
+
+impl<T: Clone + std::ops::Neg<Output = T>> std::ops::Neg for &Trace<T> {
+    type Output = Trace<T>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Trace {
+            value: -self.value.clone(),
+            tangent: -self.tangent.clone(),
+        }
+    }
+}
+
+impl<
+        T: Clone
+            + std::ops::Mul<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Div<Output = T>,
+    > std::ops::Div for Trace<T>
+{
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        // quotient rule: d(a/b) = (da*b - a*db) / b^2
+        let value = self.value.clone() / other.value.clone();
+        let tangent = (self.tangent * other.value.clone() - self.value * other.tangent.clone())
+            / (other.value.clone() * other.value);
+        Trace { value, tangent }
+    }
+}
+
+impl<
+        T: Clone
+            + std::ops::Mul<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Div<Output = T>,
+    > std::ops::Div for &Trace<T>
+{
+    type Output = Trace<T>;
+
+    #[inline]
+    fn div(self, other: Self) -> Self::Output {
+        let value = self.value.clone() / other.value.clone();
+        let tangent = (self.tangent.clone() * other.value.clone()
+            - self.value.clone() * other.tangent.clone())
+            / (other.value.clone() * other.value.clone());
+        Trace { value, tangent }
+    }
+}
+
+impl<
+        T: Clone
+            + std::ops::Mul<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Div<Output = T>,
+    > std::ops::DivAssign for Trace<T>
+{
+    #[inline]
+    fn div_assign(&mut self, other: Self) {
+        *self = self.clone() / other;
+    }
+}
@@ -0,0 +1,331 @@
+// A thin matrix/tensor wrapper over `Reverse<T>`, in the spirit of easy_ml's differentiable
+// `Matrix` and quick_maths' `Vector<N, Var>`: a row-major buffer of `Reverse<T>` elements that
+// all share one `Tape`, plus the usual element-wise and linear-algebra ops recorded onto that
+// tape. This is what turns the crate from scalar demos (the Rosenbrock bench) into something
+// usable for small neural layers, without the caller hand-threading tape indices themselves.
+
+use crate::activation::{log_sum_exp, softmax};
+use crate::autograd::{Differentiable, Reverse, Tape};
+use num::traits::{One, Zero};
+
+/// A row-major matrix of `Reverse<T>` elements sharing one `Tape`.
+#[derive(Clone, Debug)]
+pub struct Matrix<T> {
+    data: Vec<Reverse<T>>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Build a matrix directly out of already-`Reverse` elements, row-major.
+    ///
+    /// Panics if `data.len() != rows * cols`.
+    pub fn from_elements(data: Vec<Reverse<T>>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data does not match given shape");
+        Matrix { data, rows, cols }
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> &Reverse<T> {
+        &self.data[row * self.cols + col]
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut Reverse<T> {
+        &mut self.data[row * self.cols + col]
+    }
+
+    #[inline]
+    pub fn elements(&self) -> &[Reverse<T>] {
+        &self.data
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for col in 0..self.cols {
+            for row in 0..self.rows {
+                data.push(self.get(row, col).clone());
+            }
+        }
+        Matrix {
+            data,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T: Clone + Zero + One> Matrix<T> {
+    /// Build a matrix of fresh tape-tracked variables from plain values, all sharing `tape`.
+    pub fn reversible(values: Vec<T>, rows: usize, cols: usize, tape: Tape<T>) -> Self {
+        assert_eq!(values.len(), rows * cols, "values do not match given shape");
+        let data = values
+            .into_iter()
+            .map(|v| Reverse::reversible(v, tape.clone()))
+            .collect();
+        Matrix { data, rows, cols }
+    }
+}
+
+impl<T: Clone + Zero + One> Matrix<T> {
+    /// Build a constant matrix (not recorded on any tape) from plain values.
+    pub fn auto(values: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(values.len(), rows * cols, "values do not match given shape");
+        let data = values.into_iter().map(Reverse::auto).collect();
+        Matrix { data, rows, cols }
+    }
+
+    /// Multiply every element by a scalar, broadcasting it across the whole matrix.
+    pub fn scale(&self, scalar: &Reverse<T>) -> Self
+    where
+        T: std::ops::Mul,
+    {
+        let data = self.data.iter().map(|e| e * scalar).collect();
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Sum of every element, as a single scalar on the shared tape.
+    pub fn sum(&self) -> Reverse<T> {
+        let mut total = Reverse::auto(T::zero());
+        for e in &self.data {
+            total = &total + e;
+        }
+        total
+    }
+
+    /// Column vector (`rows x 1`) of the sum of each row.
+    pub fn row_sums(&self) -> Self {
+        let mut data = Vec::with_capacity(self.rows);
+        for row in 0..self.rows {
+            let mut total = Reverse::auto(T::zero());
+            for col in 0..self.cols {
+                total = &total + self.get(row, col);
+            }
+            data.push(total);
+        }
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: 1,
+        }
+    }
+
+    /// Row vector (`1 x cols`) of the sum of each column.
+    pub fn col_sums(&self) -> Self {
+        let mut data = Vec::with_capacity(self.cols);
+        for col in 0..self.cols {
+            let mut total = Reverse::auto(T::zero());
+            for row in 0..self.rows {
+                total = &total + self.get(row, col);
+            }
+            data.push(total);
+        }
+        Matrix {
+            data,
+            rows: 1,
+            cols: self.cols,
+        }
+    }
+
+    pub fn matmul(&self, other: &Self) -> Self
+    where
+        T: std::ops::Mul,
+    {
+        assert_eq!(
+            self.cols, other.rows,
+            "matmul shape mismatch: {}x{} * {}x{}",
+            self.rows, self.cols, other.rows, other.cols
+        );
+        let mut data = Vec::with_capacity(self.rows * other.cols);
+        for row in 0..self.rows {
+            for col in 0..other.cols {
+                let mut total = Reverse::auto(T::zero());
+                for k in 0..self.cols {
+                    total = &total + &(self.get(row, k) * other.get(k, col));
+                }
+                data.push(total);
+            }
+        }
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: other.cols,
+        }
+    }
+
+    /// Runs the reverse pass once per entry of `outputs`, stacking each resulting gradient (with
+    /// respect to this matrix's elements) as a row. Shape is `outputs.len() x (rows*cols)`.
+    pub fn jacobian(&self, outputs: &[Reverse<T>]) -> Self {
+        let mut data = Vec::with_capacity(outputs.len() * self.data.len());
+        for out in outputs {
+            let derivatives = out.derivatives();
+            for elem in &self.data {
+                let d = if derivatives.derivatives.is_empty() || elem.tape.is_none() {
+                    T::zero()
+                } else {
+                    derivatives.derivatives[elem.index].clone()
+                };
+                data.push(Reverse::auto(d));
+            }
+        }
+        Matrix {
+            data,
+            rows: outputs.len(),
+            cols: self.data.len(),
+        }
+    }
+
+    /// Convenience for the common case of a scalar `output`: the gradient of `output` with
+    /// respect to this matrix's elements, laid back out in this matrix's own shape.
+    pub fn grad(&self, output: &Reverse<T>) -> Self {
+        let mut gradient = self.jacobian(std::slice::from_ref(output));
+        gradient.rows = self.rows;
+        gradient.cols = self.cols;
+        gradient
+    }
+}
+
+impl<
+        T: Differentiable
+            + Clone
+            + PartialOrd
+            + One
+            + Zero
+            + std::ops::Neg<Output = T>
+            + std::ops::Add<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Mul<Output = T>
+            + std::ops::Div<Output = T>,
+    > Matrix<T>
+{
+    pub fn sigmoid(&self) -> Self {
+        self.map(Reverse::sigmoid)
+    }
+
+    pub fn tanh(&self) -> Self {
+        self.map(Reverse::tanh)
+    }
+
+    pub fn relu(&self) -> Self {
+        self.map(Reverse::relu)
+    }
+
+    pub fn leaky_relu(&self, alpha: T) -> Self {
+        self.map(|e| e.leaky_relu(alpha.clone()))
+    }
+
+    fn map(&self, f: impl Fn(&Reverse<T>) -> Reverse<T>) -> Self {
+        Matrix {
+            data: self.data.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Column vector (`rows x 1`) of the numerically stable log-sum-exp of each row.
+    pub fn row_log_sum_exp(&self) -> Self {
+        let mut data = Vec::with_capacity(self.rows);
+        for row in 0..self.rows {
+            data.push(log_sum_exp(&self.data[row * self.cols..(row + 1) * self.cols]));
+        }
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: 1,
+        }
+    }
+
+    /// Applies numerically stable softmax to each row independently.
+    pub fn row_softmax(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for row in 0..self.rows {
+            data.extend(softmax(&self.data[row * self.cols..(row + 1) * self.cols]));
+        }
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone + One + Zero + std::ops::Add> std::ops::Add for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    #[inline]
+    fn add(self, other: Self) -> Matrix<T> {
+        assert_eq!(self.rows, other.rows);
+        assert_eq!(self.cols, other.cols);
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(a, b)| a + b)
+            .collect();
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<
+        T: Clone + One + Zero + std::ops::Neg + std::ops::Neg<Output = T> + std::ops::Sub<Output = T>,
+    > std::ops::Sub for &Matrix<T>
+{
+    type Output = Matrix<T>;
+
+    #[inline]
+    fn sub(self, other: Self) -> Matrix<T> {
+        assert_eq!(self.rows, other.rows);
+        assert_eq!(self.cols, other.cols);
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(a, b)| a - b)
+            .collect();
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone + One + Zero + std::ops::Mul> std::ops::Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// Element-wise (Hadamard) product. Use `matmul` for matrix multiplication.
+    #[inline]
+    fn mul(self, other: Self) -> Matrix<T> {
+        assert_eq!(self.rows, other.rows);
+        assert_eq!(self.cols, other.cols);
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(a, b)| a * b)
+            .collect();
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
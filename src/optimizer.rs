@@ -134,3 +134,263 @@ impl<T: Real + Clone + One + std::ops::Neg<Output = T> + std::ops::Mul<Output =
         self.t = self.t.clone() + T::one();
     }
 }
+
+/// A learning-rate schedule, consulted by an optimizer's internal step counter `t` (which starts
+/// at `T::zero()` and increases by one every `step()`).
+pub trait Schedule<T> {
+    fn rate(&self, t: T) -> T;
+}
+
+/// A schedule that always returns the same learning rate, i.e. no schedule at all.
+pub struct ConstantSchedule<T> {
+    pub learning_rate: T,
+}
+
+impl<T: Clone> Schedule<T> for ConstantSchedule<T> {
+    fn rate(&self, _t: T) -> T {
+        self.learning_rate.clone()
+    }
+}
+
+/// Drops the learning rate by `drop_factor` every `step_size` steps.
+pub struct StepDecay<T> {
+    pub initial: T,
+    pub drop_factor: T,
+    pub step_size: T,
+}
+
+impl<T: Real> Schedule<T> for StepDecay<T> {
+    fn rate(&self, t: T) -> T {
+        let drops = (t / self.step_size).floor().to_i32().unwrap();
+        self.initial * self.drop_factor.powi(drops)
+    }
+}
+
+/// Cosine annealing: `lr_t = lr_min + 1/2 * (lr_max - lr_min) * (1 + cos(pi * t / period))`.
+pub struct CosineAnnealing<T> {
+    pub lr_min: T,
+    pub lr_max: T,
+    pub period: T,
+}
+
+impl<T: Real> Schedule<T> for CosineAnnealing<T> {
+    fn rate(&self, t: T) -> T {
+        let pi = T::from(std::f64::consts::PI).unwrap();
+        let half = T::from(0.5).unwrap();
+        self.lr_min + half * (self.lr_max - self.lr_min) * (T::one() + (pi * t / self.period).cos())
+    }
+}
+
+/// Linearly ramps from zero up to `after`'s rate at `warmup_steps`, then delegates to `after`.
+pub struct Warmup<T, S> {
+    pub warmup_steps: T,
+    pub after: S,
+}
+
+impl<T: Real, S: Schedule<T>> Schedule<T> for Warmup<T, S> {
+    fn rate(&self, t: T) -> T {
+        if t < self.warmup_steps {
+            self.after.rate(self.warmup_steps) * (t / self.warmup_steps)
+        } else {
+            self.after.rate(t)
+        }
+    }
+}
+
+/// Scales the gradients at `param_indices` (e.g. `params.iter().map(|p| p.index)`) in place by
+/// `max_norm / max(global_l2_norm, max_norm)`, so that the global L2 norm of *those* gradients
+/// never exceeds `max_norm`. Intended as a pre-step, called before handing `derivatives` to an
+/// `Optimizer`.
+///
+/// `derivatives.derivatives` holds an adjoint for every tape node, not just the parameters, so
+/// the norm (and the scaling) is restricted to `param_indices` -- folding over the whole slice
+/// would inflate the norm with unrelated intermediate adjoints and clip too aggressively.
+pub fn clip_grad_norm<T: Real>(derivatives: &mut Derivatives<T>, param_indices: &[usize], max_norm: T) {
+    let sum_of_squares = param_indices.iter().fold(T::zero(), |acc, &idx| {
+        let g = derivatives.derivatives[idx];
+        acc + g * g
+    });
+    let global_norm = sum_of_squares.sqrt();
+    let scale = max_norm / if global_norm > max_norm { global_norm } else { max_norm };
+    for &idx in param_indices {
+        derivatives.derivatives[idx] = derivatives.derivatives[idx] * scale;
+    }
+}
+
+/// Momentum gradient descent: `v = mu*v + g`, `p -= lr*v`.
+pub struct Momentum<T, S = ConstantSchedule<T>> {
+    schedule: S,
+    mu: T,
+    t: T,
+    velocity: Vec<T>,
+}
+
+impl<T: NumCast + Zero> Momentum<T, ConstantSchedule<T>> {
+    pub fn default(learning_rate: f64, mu: f64) -> Self {
+        Self {
+            schedule: ConstantSchedule {
+                learning_rate: T::from(learning_rate).unwrap(),
+            },
+            mu: T::from(mu).unwrap(),
+            t: T::zero(),
+            velocity: Vec::new(),
+        }
+    }
+}
+
+impl<T: Zero, S> Momentum<T, S> {
+    pub fn with_schedule(schedule: S, mu: T) -> Self {
+        Self {
+            schedule,
+            mu,
+            t: T::zero(),
+            velocity: Vec::new(),
+        }
+    }
+}
+
+impl<T: Real, S: Schedule<T>> Optimizer<T> for Momentum<T, S> {
+    fn step(&mut self, derivatives: &Derivatives<T>, params: &mut [&mut Reverse<T>]) {
+        let derivatives: &[T] = &derivatives.derivatives;
+        let nderivatives = derivatives.len();
+
+        if self.velocity.is_empty() {
+            self.velocity = vec![T::zero(); nderivatives];
+        }
+
+        for idx in 0..nderivatives {
+            self.velocity[idx] = self.mu * self.velocity[idx] + derivatives[idx];
+        }
+
+        let learning_rate = self.schedule.rate(self.t);
+        for p in params.iter_mut() {
+            let p: &mut Reverse<T> = *p;
+            let idx = p.index;
+            *p = p.clone() - Reverse::auto(learning_rate * self.velocity[idx]);
+        }
+
+        self.t = self.t + T::one();
+    }
+}
+
+/// RMSProp: running mean square `s = rho*s + (1-rho)*g^2`, step `p -= lr*g/(sqrt(s)+eps)`.
+pub struct RMSProp<T, S = ConstantSchedule<T>> {
+    schedule: S,
+    rho: T,
+    epsilon: T,
+    t: T,
+    mean_square: Vec<T>,
+}
+
+impl<T: NumCast + Zero> RMSProp<T, ConstantSchedule<T>> {
+    pub fn default(learning_rate: f64) -> Self {
+        Self {
+            schedule: ConstantSchedule {
+                learning_rate: T::from(learning_rate).unwrap(),
+            },
+            rho: T::from(0.9).unwrap(),
+            epsilon: T::from(1e-8).unwrap(),
+            t: T::zero(),
+            mean_square: Vec::new(),
+        }
+    }
+}
+
+impl<T: Zero, S> RMSProp<T, S> {
+    pub fn with_schedule(schedule: S, rho: T, epsilon: T) -> Self {
+        Self {
+            schedule,
+            rho,
+            epsilon,
+            t: T::zero(),
+            mean_square: Vec::new(),
+        }
+    }
+}
+
+impl<T: Real, S: Schedule<T>> Optimizer<T> for RMSProp<T, S> {
+    fn step(&mut self, derivatives: &Derivatives<T>, params: &mut [&mut Reverse<T>]) {
+        let derivatives: &[T] = &derivatives.derivatives;
+        let nderivatives = derivatives.len();
+
+        if self.mean_square.is_empty() {
+            self.mean_square = vec![T::zero(); nderivatives];
+        }
+
+        for idx in 0..nderivatives {
+            self.mean_square[idx] = self.rho * self.mean_square[idx]
+                + (T::one() - self.rho) * derivatives[idx] * derivatives[idx];
+        }
+
+        let learning_rate = self.schedule.rate(self.t);
+        for p in params.iter_mut() {
+            let p: &mut Reverse<T> = *p;
+            let idx = p.index;
+            *p = p.clone()
+                - Reverse::auto(
+                    learning_rate * derivatives[idx] / (self.mean_square[idx].sqrt() + self.epsilon),
+                );
+        }
+
+        self.t = self.t + T::one();
+    }
+}
+
+/// Adagrad: accumulated `G += g^2`, step `p -= lr*g/(sqrt(G)+eps)`.
+pub struct Adagrad<T, S = ConstantSchedule<T>> {
+    schedule: S,
+    epsilon: T,
+    t: T,
+    accumulated: Vec<T>,
+}
+
+impl<T: NumCast + Zero> Adagrad<T, ConstantSchedule<T>> {
+    pub fn default(learning_rate: f64) -> Self {
+        Self {
+            schedule: ConstantSchedule {
+                learning_rate: T::from(learning_rate).unwrap(),
+            },
+            epsilon: T::from(1e-8).unwrap(),
+            t: T::zero(),
+            accumulated: Vec::new(),
+        }
+    }
+}
+
+impl<T: Zero, S> Adagrad<T, S> {
+    pub fn with_schedule(schedule: S, epsilon: T) -> Self {
+        Self {
+            schedule,
+            epsilon,
+            t: T::zero(),
+            accumulated: Vec::new(),
+        }
+    }
+}
+
+impl<T: Real, S: Schedule<T>> Optimizer<T> for Adagrad<T, S> {
+    fn step(&mut self, derivatives: &Derivatives<T>, params: &mut [&mut Reverse<T>]) {
+        let derivatives: &[T] = &derivatives.derivatives;
+        let nderivatives = derivatives.len();
+
+        if self.accumulated.is_empty() {
+            self.accumulated = vec![T::zero(); nderivatives];
+        }
+
+        for idx in 0..nderivatives {
+            self.accumulated[idx] = self.accumulated[idx] + derivatives[idx] * derivatives[idx];
+        }
+
+        let learning_rate = self.schedule.rate(self.t);
+        for p in params.iter_mut() {
+            let p: &mut Reverse<T> = *p;
+            let idx = p.index;
+            *p = p.clone()
+                - Reverse::auto(
+                    learning_rate * derivatives[idx] / (self.accumulated[idx].sqrt() + self.epsilon),
+                );
+        }
+
+        self.t = self.t + T::one();
+    }
+}